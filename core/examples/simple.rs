@@ -23,7 +23,7 @@ async fn main() -> anyhow::Result<()> {
             ToolCall {
                 tool_name: "read".to_string(),
                 parameters: serde_json::json!({
-                    "path": "/tmp/test.txt"
+                    "path": "test.txt"
                 }),
             },
         ],
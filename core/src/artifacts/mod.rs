@@ -0,0 +1,247 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Outputs at or above this size (in bytes, measured as serialized JSON) are
+/// externalized into the artifact store instead of inlined into
+/// `TaskResult::outputs`.
+pub const INLINE_THRESHOLD_BYTES: usize = 4096;
+
+/// A reference to a stored artifact, returned in place of raw bytes once an
+/// output reaches [`INLINE_THRESHOLD_BYTES`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactRef {
+    pub artifact: String,
+    pub size: u64,
+    pub mime: String,
+}
+
+struct ArtifactEntry {
+    path: PathBuf,
+}
+
+/// Content-addressed store for large tool outputs. Blobs are hashed with
+/// BLAKE3 and kept as a single file per digest; an index of digest -> path/size/mime/
+/// refcount/first-seen lives in a SQLite table alongside the memory store.
+///
+/// The connection is held behind a `Mutex` rather than a bare `Connection`
+/// so that `ArtifactStore` is `Sync` and can be shared via `Arc` across
+/// `tokio::spawn` boundaries (`rusqlite::Connection` is `Send` but not
+/// `Sync`). All access is synchronous, so the lock is never held across an
+/// `.await` point.
+#[derive(Debug)]
+pub struct ArtifactStore {
+    conn: Mutex<Connection>,
+    blob_root: PathBuf,
+}
+
+impl ArtifactStore {
+    /// Open (or create) the artifact index at `db_path`, storing blob
+    /// contents under `blob_root`.
+    pub async fn new(db_path: &str, blob_root: impl Into<PathBuf>) -> Result<Self> {
+        let conn = if db_path == ":memory:" {
+            Connection::open_in_memory()?
+        } else {
+            Connection::open(Path::new(db_path))?
+        };
+
+        let blob_root = blob_root.into();
+        tokio::fs::create_dir_all(&blob_root)
+            .await
+            .with_context(|| format!("creating artifact blob root '{}'", blob_root.display()))?;
+
+        let store = Self {
+            conn: Mutex::new(conn),
+            blob_root,
+        };
+        store.initialize_schema()?;
+        Ok(store)
+    }
+
+    fn initialize_schema(&self) -> Result<()> {
+        self.conn.lock().unwrap().execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS artifacts (
+                digest TEXT PRIMARY KEY,
+                path TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                mime TEXT NOT NULL,
+                refcount INTEGER NOT NULL DEFAULT 1,
+                first_seen INTEGER NOT NULL
+            );
+            "#,
+        )?;
+
+        Ok(())
+    }
+
+    /// Store `bytes` keyed by its BLAKE3 digest, deduplicating against any
+    /// existing artifact with the same content (bumping its refcount instead
+    /// of writing a second copy).
+    ///
+    /// The index row is written with an atomic upsert rather than a
+    /// check-then-insert: under concurrent `put` calls for identical content
+    /// (steps in the same execution batch can easily produce the same
+    /// output), a separate `lookup` followed by `INSERT` races and the
+    /// loser fails on the `digest` primary key. Writing the blob itself is
+    /// naturally idempotent since the path is content-addressed, so it's
+    /// safe to (re)write it unconditionally before the upsert.
+    pub async fn put(&self, bytes: &[u8], mime: &str) -> Result<ArtifactRef> {
+        let digest = blake3::hash(bytes).to_hex().to_string();
+        let path = self.blob_root.join(&digest);
+        tokio::fs::write(&path, bytes).await?;
+
+        self.conn.lock().unwrap().execute(
+            r#"
+            INSERT INTO artifacts (digest, path, size, mime, refcount, first_seen)
+            VALUES (?1, ?2, ?3, ?4, 1, ?5)
+            ON CONFLICT(digest) DO UPDATE SET refcount = refcount + 1
+            "#,
+            params![
+                digest,
+                path.to_string_lossy(),
+                bytes.len() as i64,
+                mime,
+                chrono::Utc::now().timestamp(),
+            ],
+        )?;
+
+        Ok(ArtifactRef {
+            artifact: digest,
+            size: bytes.len() as u64,
+            mime: mime.to_string(),
+        })
+    }
+
+    /// Read back a previously stored artifact's bytes by digest.
+    pub async fn get(&self, digest: &str) -> Result<Vec<u8>> {
+        let entry = self
+            .lookup(digest)?
+            .ok_or_else(|| anyhow::anyhow!("unknown artifact '{}'", digest))?;
+
+        tokio::fs::read(&entry.path)
+            .await
+            .with_context(|| format!("reading artifact '{}'", digest))
+    }
+
+    /// Externalize `value` into the artifact store if its serialized size is
+    /// at or above [`INLINE_THRESHOLD_BYTES`], returning a reference object
+    /// in its place. Small values pass through unchanged.
+    pub async fn maybe_externalize(&self, value: serde_json::Value) -> Result<serde_json::Value> {
+        let serialized = serde_json::to_vec(&value)?;
+        if serialized.len() < INLINE_THRESHOLD_BYTES {
+            return Ok(value);
+        }
+
+        let artifact_ref = self.put(&serialized, "application/json").await?;
+        Ok(serde_json::to_value(artifact_ref)?)
+    }
+
+    fn lookup(&self, digest: &str) -> Result<Option<ArtifactEntry>> {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT path FROM artifacts WHERE digest = ?1",
+                params![digest],
+                |row| {
+                    Ok(ArtifactEntry {
+                        path: PathBuf::from(row.get::<_, String>(0)?),
+                    })
+                },
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                e => Err(e.into()),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn temp_store() -> (ArtifactStore, PathBuf) {
+        let root = std::env::temp_dir().join(format!("nova-artifacts-test-{}", uuid::Uuid::new_v4()));
+        let store = ArtifactStore::new(":memory:", root.clone()).await.unwrap();
+        (store, root)
+    }
+
+    #[tokio::test]
+    async fn put_then_get_round_trips() {
+        let (store, root) = temp_store().await;
+
+        let artifact_ref = store.put(b"hello world", "text/plain").await.unwrap();
+        assert_eq!(artifact_ref.size, 11);
+
+        let bytes = store.get(&artifact_ref.artifact).await.unwrap();
+        assert_eq!(bytes, b"hello world");
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn put_deduplicates_identical_content() {
+        let (store, root) = temp_store().await;
+
+        let first = store.put(b"duplicate me", "text/plain").await.unwrap();
+        let second = store.put(b"duplicate me", "text/plain").await.unwrap();
+        assert_eq!(first.artifact, second.artifact);
+
+        let mut entries = tokio::fs::read_dir(&root).await.unwrap();
+        let mut count = 0;
+        while entries.next_entry().await.unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 1);
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn small_values_are_not_externalized() {
+        let (store, root) = temp_store().await;
+
+        let value = serde_json::json!({"ok": true});
+        let result = store.maybe_externalize(value.clone()).await.unwrap();
+        assert_eq!(result, value);
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_put_of_identical_content_does_not_race() {
+        let (store, root) = temp_store().await;
+        let store = std::sync::Arc::new(store);
+
+        let handles: Vec<_> = (0..20)
+            .map(|_| {
+                let store = store.clone();
+                tokio::spawn(async move { store.put(b"same content, many writers", "text/plain").await })
+            })
+            .collect();
+
+        let mut digests = std::collections::HashSet::new();
+        for handle in handles {
+            let artifact_ref = handle.await.unwrap().unwrap();
+            digests.insert(artifact_ref.artifact);
+        }
+        assert_eq!(digests.len(), 1);
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn large_values_are_externalized() {
+        let (store, root) = temp_store().await;
+
+        let value = serde_json::json!({"data": "x".repeat(INLINE_THRESHOLD_BYTES)});
+        let result = store.maybe_externalize(value).await.unwrap();
+        assert!(result.get("artifact").is_some());
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+}
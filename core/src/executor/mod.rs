@@ -1,19 +1,24 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tokio::sync::RwLock;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::{RwLock, Semaphore};
 
 /// Parallel task executor with dependency resolution
 #[derive(Debug)]
 pub struct Executor {
     config: ExecutorConfig,
     active_tasks: RwLock<HashMap<String, TaskStatus>>,
+    metrics: Arc<crate::metrics::Metrics>,
+    artifacts: Arc<crate::artifacts::ArtifactStore>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutorConfig {
     pub max_parallel: usize,
     pub default_timeout_ms: u64,
+    /// Root directory the `read`/`write` tools are confined to.
+    pub workspace_root: std::path::PathBuf,
 }
 
 impl Default for ExecutorConfig {
@@ -21,12 +26,15 @@ impl Default for ExecutorConfig {
         Self {
             max_parallel: 10,
             default_timeout_ms: 30000,
+            workspace_root: std::path::PathBuf::from("."),
         }
     }
 }
 
-#[derive(Debug, Clone)]
-enum TaskStatus {
+/// Lifecycle state of a task tracked by the executor, surfaced via
+/// [`Executor::task_status`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskStatus {
     Pending,
     Running,
     Completed,
@@ -34,13 +42,44 @@ enum TaskStatus {
 }
 
 impl Executor {
-    pub fn new(config: ExecutorConfig) -> Self {
+    pub fn new(
+        config: ExecutorConfig,
+        metrics: Arc<crate::metrics::Metrics>,
+        artifacts: Arc<crate::artifacts::ArtifactStore>,
+    ) -> Self {
         Self {
             config,
             active_tasks: RwLock::new(HashMap::new()),
+            metrics,
+            artifacts,
         }
     }
 
+    /// Mark `task_id` as accepted but not yet executing, e.g. while it's
+    /// still being planned and authorized upstream. A later call to
+    /// [`Executor::execute`] for the same id overwrites this with `Running`.
+    pub async fn mark_pending(&self, task_id: &str) {
+        self.active_tasks
+            .write()
+            .await
+            .insert(task_id.to_string(), TaskStatus::Pending);
+    }
+
+    /// Mark `task_id` as failed, e.g. when planning or authorization rejects
+    /// it before [`Executor::execute`] ever runs.
+    pub async fn mark_failed(&self, task_id: &str, reason: String) {
+        self.active_tasks
+            .write()
+            .await
+            .insert(task_id.to_string(), TaskStatus::Failed(reason));
+    }
+
+    /// Look up the last known status of a task by id, if the executor has
+    /// seen it.
+    pub async fn task_status(&self, task_id: &str) -> Option<TaskStatus> {
+        self.active_tasks.read().await.get(task_id).cloned()
+    }
+
     /// Execute a plan with intelligent parallel/serial execution
     pub async fn execute(
         &self,
@@ -49,29 +88,69 @@ impl Executor {
     ) -> Result<crate::TaskResult> {
         tracing::info!("Executing plan with {} steps", plan.steps.len());
 
-        let mut outputs = Vec::new();
+        let task_id = plan.task_id.clone();
+        self.active_tasks
+            .write()
+            .await
+            .insert(task_id.clone(), TaskStatus::Running);
+        self.metrics.task_started();
+
         let start = std::time::Instant::now();
+        let outcome = self.run_plan(plan, tools).await;
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        self.metrics.task_finished(outcome.is_ok(), duration_ms);
+        let final_status = match &outcome {
+            Ok(_) => TaskStatus::Completed,
+            Err(e) => TaskStatus::Failed(e.to_string()),
+        };
+        self.active_tasks
+            .write()
+            .await
+            .insert(task_id.clone(), final_status);
+
+        let outputs = outcome?;
+
+        Ok(crate::TaskResult {
+            task_id,
+            success: true,
+            outputs,
+            duration_ms,
+        })
+    }
+
+    async fn run_plan(
+        &self,
+        plan: crate::planner::ExecutionPlan,
+        tools: &crate::tools::ToolRegistry,
+    ) -> Result<Vec<serde_json::Value>> {
+        let mut outputs = Vec::new();
 
-        // Build dependency graph
-        let graph = self.build_dependency_graph(&plan);
+        // Build dependency graph and compute parallel execution waves
+        let graph = self.build_dependency_graph(&plan)?;
+        let batches = graph.execution_batches()?;
+        let permits = Arc::new(Semaphore::new(self.config.max_parallel.max(1)));
 
-        // Execute based on dependencies
-        for batch in graph.execution_batches() {
+        for batch in batches {
             if batch.len() == 1 {
                 // Serial execution
                 let step = &batch[0];
                 let output = self.execute_step(step, tools).await?;
                 outputs.push(output);
             } else {
-                // Parallel execution
+                // Parallel execution, capped at `max_parallel` concurrent spawns
                 let handles: Vec<_> = batch
                     .iter()
                     .map(|step| {
                         let step = step.clone();
                         let tools = tools.clone();
+                        let timeout_ms = self.config.default_timeout_ms;
+                        let permits = permits.clone();
+                        let metrics = self.metrics.clone();
+                        let artifacts = self.artifacts.clone();
                         tokio::spawn(async move {
-                            // Execute step
-                            Ok::<_, anyhow::Error>(serde_json::json!({}))
+                            let _permit = permits.acquire_owned().await?;
+                            Executor::run_step(&step, &tools, timeout_ms, &metrics, &artifacts).await
                         })
                     })
                     .collect();
@@ -83,14 +162,7 @@ impl Executor {
             }
         }
 
-        let duration_ms = start.elapsed().as_millis() as u64;
-
-        Ok(crate::TaskResult {
-            task_id: plan.task_id,
-            success: true,
-            outputs,
-            duration_ms,
-        })
+        Ok(outputs)
     }
 
     async fn execute_step(
@@ -98,27 +170,230 @@ impl Executor {
         step: &crate::planner::ExecutionStep,
         tools: &crate::tools::ToolRegistry,
     ) -> Result<serde_json::Value> {
-        // TODO: Implement actual step execution
-        Ok(serde_json::json!({}))
+        Executor::run_step(
+            step,
+            tools,
+            self.config.default_timeout_ms,
+            &self.metrics,
+            &self.artifacts,
+        )
+        .await
     }
 
-    fn build_dependency_graph(&self, plan: &crate::planner::ExecutionPlan) -> DependencyGraph {
-        // TODO: Implement dependency analysis
-        DependencyGraph::new()
+    /// Run a single step. Free of `&self` so it can be driven from inside a
+    /// `tokio::spawn`'d task as well as the serial path. Large outputs are
+    /// externalized into `artifacts` rather than returned inline.
+    async fn run_step(
+        step: &crate::planner::ExecutionStep,
+        tools: &crate::tools::ToolRegistry,
+        timeout_ms: u64,
+        metrics: &crate::metrics::Metrics,
+        artifacts: &crate::artifacts::ArtifactStore,
+    ) -> Result<serde_json::Value> {
+        metrics.record_tool_invocation(&step.tool_name);
+
+        let executor = tools
+            .get_executor(&step.tool_name)
+            .ok_or_else(|| anyhow::anyhow!("no executor registered for tool '{}'", step.tool_name))?;
+
+        let invocation = executor.invoke(&step.parameters);
+        let output = match tokio::time::timeout(std::time::Duration::from_millis(timeout_ms), invocation).await {
+            Ok(result) => result?,
+            Err(_) => anyhow::bail!(
+                "tool '{}' timed out after {}ms",
+                step.tool_name,
+                timeout_ms
+            ),
+        };
+
+        artifacts.maybe_externalize(output).await
+    }
+
+    fn build_dependency_graph(&self, plan: &crate::planner::ExecutionPlan) -> Result<DependencyGraph> {
+        DependencyGraph::build(&plan.steps)
     }
 }
 
+/// A DAG over a plan's steps, keyed by `ExecutionStep::id`, used to compute
+/// parallel execution waves via Kahn's algorithm.
 struct DependencyGraph {
-    // TODO: Implement graph structure
+    steps: HashMap<String, crate::planner::ExecutionStep>,
+    in_degree: HashMap<String, usize>,
+    /// dependency id -> ids of steps that depend on it
+    dependents: HashMap<String, Vec<String>>,
 }
 
 impl DependencyGraph {
-    fn new() -> Self {
-        Self {}
+    fn build(steps: &[crate::planner::ExecutionStep]) -> Result<Self> {
+        let known_ids: HashSet<&str> = steps.iter().map(|s| s.id.as_str()).collect();
+
+        let mut step_map = HashMap::new();
+        let mut in_degree = HashMap::new();
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+
+        for step in steps {
+            for dep in &step.dependencies {
+                if !known_ids.contains(dep.as_str()) {
+                    anyhow::bail!(
+                        "step '{}' declares dependency on unknown step '{}'",
+                        step.id,
+                        dep
+                    );
+                }
+            }
+
+            in_degree.insert(step.id.clone(), step.dependencies.len());
+            step_map.insert(step.id.clone(), step.clone());
+        }
+
+        for step in steps {
+            for dep in &step.dependencies {
+                dependents.entry(dep.clone()).or_default().push(step.id.clone());
+            }
+        }
+
+        Ok(Self {
+            steps: step_map,
+            in_degree,
+            dependents,
+        })
+    }
+
+    /// Compute execution waves with Kahn's algorithm: each batch holds every
+    /// step whose in-degree is currently zero, after which their dependents'
+    /// in-degrees are decremented to find the next batch. Any steps left over
+    /// once no batch can be formed indicate a dependency cycle.
+    fn execution_batches(&self) -> Result<Vec<Vec<crate::planner::ExecutionStep>>> {
+        let mut in_degree = self.in_degree.clone();
+        let mut remaining: HashSet<String> = self.steps.keys().cloned().collect();
+        let mut batches = Vec::new();
+
+        while !remaining.is_empty() {
+            let mut ready: Vec<String> = remaining
+                .iter()
+                .filter(|id| in_degree[id.as_str()] == 0)
+                .cloned()
+                .collect();
+
+            if ready.is_empty() {
+                let mut stuck: Vec<&str> = remaining.iter().map(String::as_str).collect();
+                stuck.sort();
+                anyhow::bail!(
+                    "dependency cycle detected among steps: {}",
+                    stuck.join(", ")
+                );
+            }
+            ready.sort();
+
+            for id in &ready {
+                remaining.remove(id);
+                if let Some(affected) = self.dependents.get(id) {
+                    for dependent in affected {
+                        if let Some(count) = in_degree.get_mut(dependent) {
+                            *count -= 1;
+                        }
+                    }
+                }
+            }
+
+            let batch = ready.iter().map(|id| self.steps[id].clone()).collect();
+            batches.push(batch);
+        }
+
+        Ok(batches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planner::{ExecutionPlan, ExecutionStep};
+
+    async fn test_executor() -> (Executor, std::path::PathBuf) {
+        let root = std::env::temp_dir().join(format!("nova-executor-test-{}", uuid::Uuid::new_v4()));
+        let artifacts = Arc::new(
+            crate::artifacts::ArtifactStore::new(":memory:", root.join("artifacts"))
+                .await
+                .unwrap(),
+        );
+        let metrics = Arc::new(crate::metrics::Metrics::new());
+        let executor = Executor::new(ExecutorConfig::default(), metrics, artifacts);
+        (executor, root)
+    }
+
+    #[tokio::test]
+    async fn mark_pending_is_visible_before_execution_starts() {
+        let (executor, root) = test_executor().await;
+
+        executor.mark_pending("t1").await;
+        assert_eq!(executor.task_status("t1").await, Some(TaskStatus::Pending));
+
+        let tools = crate::tools::ToolRegistry::new(crate::security::SandboxMode::None, root.clone());
+        let plan = ExecutionPlan {
+            task_id: "t1".to_string(),
+            steps: vec![],
+        };
+        executor.execute(plan, &tools).await.unwrap();
+        assert_eq!(executor.task_status("t1").await, Some(TaskStatus::Completed));
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_task_status_carries_the_error_message() {
+        let (executor, root) = test_executor().await;
+
+        let tools = crate::tools::ToolRegistry::new(crate::security::SandboxMode::None, root.clone());
+        let plan = ExecutionPlan {
+            task_id: "t2".to_string(),
+            steps: vec![ExecutionStep {
+                id: "step-0".to_string(),
+                tool_name: "does-not-exist".to_string(),
+                parameters: serde_json::json!({}),
+                dependencies: vec![],
+            }],
+        };
+        assert!(executor.execute(plan, &tools).await.is_err());
+
+        match executor.task_status("t2").await {
+            Some(TaskStatus::Failed(message)) => assert!(message.contains("does-not-exist")),
+            other => panic!("expected Failed status, got {:?}", other),
+        }
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    fn step(id: &str, deps: &[&str]) -> ExecutionStep {
+        ExecutionStep {
+            id: id.to_string(),
+            tool_name: "read".to_string(),
+            parameters: serde_json::json!({}),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn batches_independent_steps_together() {
+        let steps = vec![step("a", &[]), step("b", &[]), step("c", &["a", "b"])];
+        let graph = DependencyGraph::build(&steps).unwrap();
+        let batches = graph.execution_batches().unwrap();
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), 2);
+        assert_eq!(batches[1].len(), 1);
+        assert_eq!(batches[1][0].id, "c");
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let steps = vec![step("a", &["b"]), step("b", &["a"])];
+        let graph = DependencyGraph::build(&steps).unwrap();
+        assert!(graph.execution_batches().is_err());
     }
 
-    fn execution_batches(&self) -> Vec<Vec<crate::planner::ExecutionStep>> {
-        // TODO: Return batches of independent steps
-        vec![]
+    #[test]
+    fn rejects_unknown_dependency() {
+        let steps = vec![step("a", &["missing"])];
+        assert!(DependencyGraph::build(&steps).is_err());
     }
 }
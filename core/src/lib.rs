@@ -1,11 +1,15 @@
+pub mod artifacts;
 pub mod executor;
 pub mod memory;
+pub mod metrics;
 pub mod planner;
+pub mod scheduler;
 pub mod security;
 pub mod tools;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// Core runtime for Nova agent execution
 #[derive(Debug)]
@@ -15,16 +19,31 @@ pub struct Runtime {
     security: security::SecurityManager,
     tools: tools::ToolRegistry,
     planner: planner::Planner,
+    scheduler: Arc<scheduler::Scheduler>,
+    metrics: Arc<metrics::Metrics>,
+    artifacts: Arc<artifacts::ArtifactStore>,
 }
 
 impl Runtime {
     /// Create a new runtime instance
     pub async fn new(config: RuntimeConfig) -> Result<Self> {
         let memory = memory::MemoryStore::new(&config.memory_path).await?;
-        let security = security::SecurityManager::new(config.security);
-        let tools = tools::ToolRegistry::new();
-        let executor = executor::Executor::new(config.executor);
+        let artifacts = Arc::new(
+            artifacts::ArtifactStore::new(
+                &config.memory_path,
+                config.executor.workspace_root.join(".nova-artifacts"),
+            )
+            .await?,
+        );
+        let tools = tools::ToolRegistry::new(
+            config.security.sandbox_mode.clone(),
+            config.executor.workspace_root.clone(),
+        );
+        let security = security::SecurityManager::new(config.security)?;
+        let metrics = Arc::new(metrics::Metrics::new());
+        let executor = executor::Executor::new(config.executor, metrics.clone(), artifacts.clone());
         let planner = planner::Planner::new();
+        let scheduler = Arc::new(scheduler::Scheduler::new());
 
         Ok(Self {
             executor,
@@ -32,25 +51,117 @@ impl Runtime {
             security,
             tools,
             planner,
+            scheduler,
+            metrics,
+            artifacts,
         })
     }
 
     /// Execute a task with the given context
     pub async fn execute(&self, task: Task) -> Result<TaskResult> {
+        self.executor.mark_pending(&task.id).await;
+
         // 1. Plan the execution
-        let plan = self.planner.plan(&task).await?;
+        let plan = match self.planner.plan(&task).await {
+            Ok(plan) => plan,
+            Err(e) => {
+                self.executor.mark_failed(&task.id, e.to_string()).await;
+                return Err(e);
+            }
+        };
 
         // 2. Check security permissions
-        self.security.authorize(&plan)?;
+        if let Err(e) = self.security.authorize(&plan, &self.tools) {
+            self.executor.mark_failed(&task.id, e.to_string()).await;
+            return Err(e);
+        }
 
         // 3. Execute the plan
         let result = self.executor.execute(plan, &self.tools).await?;
 
-        // 4. Store in memory
-        self.memory.store_execution(&task, &result).await?;
+        // 4. Store in memory, then link any artifact-ref outputs to it so
+        // the artifact's contents can be traced back to the execution that
+        // produced them.
+        let memory_id = match self.memory.store_execution(&task, &result).await {
+            Ok(memory_id) => memory_id,
+            Err(e) => {
+                self.executor.mark_failed(&task.id, e.to_string()).await;
+                return Err(e);
+            }
+        };
+        for output in &result.outputs {
+            if let Some(digest) = output.get("artifact").and_then(|v| v.as_str()) {
+                if let Err(e) = self.memory.link_artifact(&memory_id, digest).await {
+                    self.executor.mark_failed(&task.id, e.to_string()).await;
+                    return Err(e);
+                }
+            }
+        }
 
         Ok(result)
     }
+
+    /// Register a task to run on a recurring or one-shot trigger. Returns a
+    /// handle that can be used to cancel the entry. Requires the runtime to
+    /// be wrapped in an `Arc` since the background loop (started separately
+    /// via [`Runtime::spawn_scheduler`]) re-enters the full
+    /// plan -> authorize -> execute -> store_execution pipeline on `self`.
+    pub async fn schedule(
+        self: &Arc<Self>,
+        task: Task,
+        trigger: scheduler::Trigger,
+    ) -> scheduler::ScheduleHandle {
+        let recurrence = match trigger {
+            scheduler::Trigger::At(_) => scheduler::Recurrence::OneShot,
+            _ => scheduler::Recurrence::Repeating,
+        };
+
+        self.scheduler.add(task, trigger, recurrence).await
+    }
+
+    /// Spawn the background loop that drives due schedule entries. Returns a
+    /// join handle and a sender that, when dropped or sent to, shuts the loop
+    /// down.
+    pub fn spawn_scheduler(
+        self: &Arc<Self>,
+    ) -> (tokio::task::JoinHandle<()>, tokio::sync::mpsc::Sender<()>) {
+        self.scheduler.clone().spawn(self.clone())
+    }
+
+    /// Persist the outcome of a scheduled run, tagged with its schedule id.
+    pub(crate) async fn store_schedule_run(
+        &self,
+        schedule_id: &str,
+        result: &TaskResult,
+    ) -> Result<()> {
+        self.memory.store_schedule_run(schedule_id, result).await
+    }
+
+    pub(crate) fn now_unix(&self) -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+
+    /// Take a serializable snapshot of execution metrics.
+    pub fn metrics_snapshot(&self) -> metrics::MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Render execution metrics in Prometheus text exposition format.
+    pub fn metrics_prometheus(&self) -> String {
+        self.metrics.render_prometheus()
+    }
+
+    /// Read back a tool output previously externalized into the artifact
+    /// store (see [`artifacts::ArtifactStore::maybe_externalize`]).
+    pub async fn read_artifact(&self, digest: &str) -> Result<Vec<u8>> {
+        self.artifacts.get(digest).await
+    }
+
+    /// Look up the last known status of a task by id, if the executor has
+    /// seen it.
+    pub async fn task_status(&self, task_id: &str) -> Option<executor::TaskStatus> {
+        self.executor.task_status(task_id).await
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
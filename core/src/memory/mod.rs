@@ -1,12 +1,86 @@
 use anyhow::Result;
 use rusqlite::{Connection, params};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Mutex;
+
+/// Number of recent memories considered as candidates for a hybrid search
+/// before ranking and truncating to the requested `limit`.
+const CANDIDATE_POOL_SIZE: usize = 500;
+
+/// Relative weight given to each of the three hybrid-search signals.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchWeights {
+    pub keyword: f32,
+    pub vector: f32,
+    pub temporal: f32,
+}
+
+impl Default for SearchWeights {
+    fn default() -> Self {
+        Self {
+            keyword: 0.5,
+            vector: 0.3,
+            temporal: 0.2,
+        }
+    }
+}
+
+/// Encode an embedding as little-endian f32 bytes for storage in the
+/// `embedding BLOB` column.
+fn serialize_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Decode an embedding previously written by [`serialize_embedding`].
+fn deserialize_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.is_empty() || b.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Min-max normalize a set of scores into `[0, 1]`. A flat input (all values
+/// equal) normalizes to all zeros rather than dividing by zero.
+fn normalize(scores: &[f32]) -> Vec<f32> {
+    let min = scores.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+
+    if range <= f32::EPSILON {
+        return vec![0.0; scores.len()];
+    }
+
+    scores.iter().map(|s| (s - min) / range).collect()
+}
 
 /// Unified memory store with vector search capabilities
+///
+/// The connection is held behind a `Mutex` rather than a bare `Connection`
+/// so that `MemoryStore` is `Sync` and can be shared via `Arc` across
+/// `tokio::spawn` boundaries (`rusqlite::Connection` is `Send` but not
+/// `Sync`). All access is synchronous, so the lock is never held across an
+/// `.await` point.
 #[derive(Debug)]
 pub struct MemoryStore {
-    conn: Connection,
+    conn: Mutex<Connection>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,13 +106,15 @@ impl MemoryStore {
             Connection::open(Path::new(path))?
         };
 
-        let store = Self { conn };
+        let store = Self {
+            conn: Mutex::new(conn),
+        };
         store.initialize_schema()?;
         Ok(store)
     }
 
     fn initialize_schema(&self) -> Result<()> {
-        self.conn.execute_batch(
+        self.conn.lock().unwrap().execute_batch(
             r#"
             CREATE TABLE IF NOT EXISTS memories (
                 id TEXT PRIMARY KEY,
@@ -64,6 +140,21 @@ impl MemoryStore {
                 content_rowid=rowid
             );
 
+            CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
+                INSERT INTO memories_fts(rowid, content, tags) VALUES (new.rowid, new.content, new.tags);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
+                INSERT INTO memories_fts(memories_fts, rowid, content, tags)
+                VALUES ('delete', old.rowid, old.content, old.tags);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
+                INSERT INTO memories_fts(memories_fts, rowid, content, tags)
+                VALUES ('delete', old.rowid, old.content, old.tags);
+                INSERT INTO memories_fts(rowid, content, tags) VALUES (new.rowid, new.content, new.tags);
+            END;
+
             CREATE TABLE IF NOT EXISTS memory_relations (
                 from_id TEXT,
                 to_id TEXT,
@@ -81,17 +172,19 @@ impl MemoryStore {
     pub async fn store(&self, memory: &Memory) -> Result<()> {
         let tags_json = serde_json::to_string(&memory.tags)?;
         let metadata_json = serde_json::to_string(&memory.metadata)?;
+        let embedding_bytes = memory.embedding.as_deref().map(serialize_embedding);
 
-        self.conn.execute(
+        self.conn.lock().unwrap().execute(
             r#"
             INSERT INTO memories (
-                id, content, timestamp, importance, decay_rate,
+                id, content, embedding, timestamp, importance, decay_rate,
                 tags, source, session_id, metadata
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
             "#,
             params![
                 memory.id,
                 memory.content,
+                embedding_bytes,
                 memory.timestamp,
                 memory.importance,
                 memory.decay_rate,
@@ -105,49 +198,166 @@ impl MemoryStore {
         Ok(())
     }
 
-    /// Retrieve memories using hybrid search
+    /// Retrieve memories using hybrid keyword + temporal search. Equivalent
+    /// to [`MemoryStore::search_hybrid`] with no query embedding.
     pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<Memory>> {
-        // TODO: Implement hybrid search (vector + keyword + temporal)
-        let mut stmt = self.conn.prepare(
+        self.search_hybrid(query, None, limit, SearchWeights::default())
+    }
+
+    /// Retrieve memories using hybrid vector + temporal search. Equivalent to
+    /// [`MemoryStore::search_hybrid`] with no keyword query.
+    pub async fn search_semantic(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+    ) -> Result<Vec<Memory>> {
+        self.search_hybrid("", Some(query_embedding), limit, SearchWeights::default())
+    }
+
+    /// Rank candidate memories by a weighted combination of three signals:
+    /// keyword relevance (FTS5 `bm25`, negated so higher is better), vector
+    /// similarity (cosine similarity against `query_embedding`), and a
+    /// recency-decayed importance (`importance * exp(-decay_rate * age_days)`).
+    /// Each signal is min-max normalized across the candidate set before
+    /// weighting, so the signals are comparable regardless of scale. The
+    /// vector signal drops out when `query_embedding` is `None` and the
+    /// keyword signal when `query` is empty; with neither, the ranking is
+    /// purely temporal.
+    fn search_hybrid(
+        &self,
+        query: &str,
+        query_embedding: Option<&[f32]>,
+        limit: usize,
+        weights: SearchWeights,
+    ) -> Result<Vec<Memory>> {
+        let candidates = self.load_candidates(CANDIDATE_POOL_SIZE)?;
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let keyword_scores = if query.trim().is_empty() {
+            HashMap::new()
+        } else {
+            self.keyword_scores(query)?
+        };
+
+        let now = chrono::Utc::now().timestamp();
+
+        let keyword_raw: Vec<f32> = candidates
+            .iter()
+            .map(|m| *keyword_scores.get(&m.id).unwrap_or(&0.0))
+            .collect();
+
+        let vector_raw: Vec<f32> = candidates
+            .iter()
+            .map(|m| match (query_embedding, &m.embedding) {
+                (Some(query_embedding), Some(embedding)) => {
+                    cosine_similarity(query_embedding, embedding)
+                }
+                _ => 0.0,
+            })
+            .collect();
+
+        let temporal_raw: Vec<f32> = candidates
+            .iter()
+            .map(|m| {
+                let age_days = ((now - m.timestamp).max(0) as f32) / 86_400.0;
+                m.importance * (-m.decay_rate * age_days).exp()
+            })
+            .collect();
+
+        let keyword_norm = normalize(&keyword_raw);
+        let vector_norm = normalize(&vector_raw);
+        let temporal_norm = normalize(&temporal_raw);
+
+        let mut scored: Vec<(f32, Memory)> = candidates
+            .into_iter()
+            .enumerate()
+            .map(|(i, memory)| {
+                let score = weights.keyword * keyword_norm[i]
+                    + weights.vector * vector_norm[i]
+                    + weights.temporal * temporal_norm[i];
+                (score, memory)
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(limit);
+
+        Ok(scored.into_iter().map(|(_, memory)| memory).collect())
+    }
+
+    /// Load the most recent memories as the candidate pool for ranking.
+    fn load_candidates(&self, pool_size: usize) -> Result<Vec<Memory>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
             r#"
-            SELECT id, content, timestamp, importance, decay_rate,
+            SELECT id, content, embedding, timestamp, importance, decay_rate,
                    tags, source, session_id, metadata
             FROM memories
-            WHERE content LIKE ?1
-            ORDER BY importance DESC, timestamp DESC
-            LIMIT ?2
+            ORDER BY timestamp DESC
+            LIMIT ?1
             "#,
         )?;
 
         let memories = stmt
-            .query_map(params![format!("%{}%", query), limit], |row| {
-                let tags_json: String = row.get(5)?;
-                let metadata_json: String = row.get(8)?;
-
-                Ok(Memory {
-                    id: row.get(0)?,
-                    content: row.get(1)?,
-                    embedding: None,
-                    timestamp: row.get(2)?,
-                    importance: row.get(3)?,
-                    decay_rate: row.get(4)?,
-                    tags: serde_json::from_str(&tags_json).unwrap_or_default(),
-                    source: row.get(6)?,
-                    session_id: row.get(7)?,
-                    metadata: serde_json::from_str(&metadata_json).unwrap_or(serde_json::json!({})),
-                })
-            })?
+            .query_map(params![pool_size], Self::row_to_memory)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(memories)
     }
 
-    /// Store task execution in memory
+    /// Query `memories_fts` for `bm25` relevance, keyed by memory id. `bm25`
+    /// is lower-is-better, so scores are negated before returning.
+    fn keyword_scores(&self, query: &str) -> Result<HashMap<String, f32>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT m.id, bm25(memories_fts)
+            FROM memories_fts
+            JOIN memories m ON m.rowid = memories_fts.rowid
+            WHERE memories_fts MATCH ?1
+            "#,
+        )?;
+
+        let scores = stmt
+            .query_map(params![query], |row| {
+                let id: String = row.get(0)?;
+                let bm25: f64 = row.get(1)?;
+                Ok((id, -bm25 as f32))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(scores)
+    }
+
+    fn row_to_memory(row: &rusqlite::Row) -> rusqlite::Result<Memory> {
+        let tags_json: String = row.get(6)?;
+        let metadata_json: String = row.get(9)?;
+        let embedding_bytes: Option<Vec<u8>> = row.get(2)?;
+
+        Ok(Memory {
+            id: row.get(0)?,
+            content: row.get(1)?,
+            embedding: embedding_bytes.map(|b| deserialize_embedding(&b)),
+            timestamp: row.get(3)?,
+            importance: row.get(4)?,
+            decay_rate: row.get(5)?,
+            tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+            source: row.get(7)?,
+            session_id: row.get(8)?,
+            metadata: serde_json::from_str(&metadata_json).unwrap_or(serde_json::json!({})),
+        })
+    }
+
+    /// Store task execution in memory, returning the id of the newly
+    /// created memory so callers (e.g. [`crate::Runtime::execute`]) can link
+    /// it to any artifacts the execution produced.
     pub async fn store_execution(
         &self,
         task: &crate::Task,
         result: &crate::TaskResult,
-    ) -> Result<()> {
+    ) -> Result<String> {
         let memory = Memory {
             id: uuid::Uuid::new_v4().to_string(),
             content: format!("Executed task: {}", task.description),
@@ -165,8 +375,57 @@ impl MemoryStore {
             }),
         };
 
+        self.store(&memory).await?;
+        Ok(memory.id)
+    }
+
+    /// Persist the outcome of a scheduled run, tagged with its schedule id so
+    /// a scheduler can reconstruct run history after a restart.
+    pub async fn store_schedule_run(
+        &self,
+        schedule_id: &str,
+        result: &crate::TaskResult,
+    ) -> Result<()> {
+        let memory = Memory {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: format!("Scheduled run {} for task: {}", schedule_id, result.task_id),
+            embedding: None,
+            timestamp: chrono::Utc::now().timestamp(),
+            importance: 0.5,
+            decay_rate: 0.1,
+            tags: vec![
+                "schedule".to_string(),
+                "execution".to_string(),
+                schedule_id.to_string(),
+            ],
+            source: "scheduler".to_string(),
+            session_id: None,
+            metadata: serde_json::json!({
+                "schedule_id": schedule_id,
+                "task_id": result.task_id,
+                "success": result.success,
+                "duration_ms": result.duration_ms,
+            }),
+        };
+
         self.store(&memory).await
     }
+
+    /// Link a memory to a content-addressed artifact (see
+    /// [`crate::artifacts::ArtifactStore`]) by digest, so the artifact's
+    /// contents can be traced back to the memory that referenced it.
+    pub async fn link_artifact(&self, memory_id: &str, digest: &str) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            r#"
+            INSERT INTO memory_relations (from_id, to_id, relation_type, strength)
+            VALUES (?1, ?2, 'artifact', 1.0)
+            ON CONFLICT (from_id, to_id) DO NOTHING
+            "#,
+            params![memory_id, digest],
+        )?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -196,4 +455,90 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].content, "Test memory");
     }
+
+    #[tokio::test]
+    async fn search_ranks_keyword_matches_above_unrelated_memories() {
+        let store = MemoryStore::new(":memory:").await.unwrap();
+
+        let matching = Memory {
+            id: "matching".to_string(),
+            content: "the quick brown fox".to_string(),
+            embedding: None,
+            timestamp: chrono::Utc::now().timestamp(),
+            importance: 0.5,
+            decay_rate: 0.1,
+            tags: vec![],
+            source: "test".to_string(),
+            session_id: None,
+            metadata: serde_json::json!({}),
+        };
+        let unrelated = Memory {
+            id: "unrelated".to_string(),
+            content: "completely different topic".to_string(),
+            embedding: None,
+            timestamp: chrono::Utc::now().timestamp(),
+            importance: 0.5,
+            decay_rate: 0.1,
+            tags: vec![],
+            source: "test".to_string(),
+            session_id: None,
+            metadata: serde_json::json!({}),
+        };
+
+        store.store(&matching).await.unwrap();
+        store.store(&unrelated).await.unwrap();
+
+        let results = store.search("fox", 10).await.unwrap();
+        assert_eq!(results[0].id, "matching");
+    }
+
+    #[tokio::test]
+    async fn search_semantic_ranks_by_cosine_similarity() {
+        let store = MemoryStore::new(":memory:").await.unwrap();
+
+        let similar = Memory {
+            id: "similar".to_string(),
+            content: "alpha".to_string(),
+            embedding: Some(vec![1.0, 0.0, 0.0]),
+            timestamp: chrono::Utc::now().timestamp(),
+            importance: 0.5,
+            decay_rate: 0.1,
+            tags: vec![],
+            source: "test".to_string(),
+            session_id: None,
+            metadata: serde_json::json!({}),
+        };
+        let dissimilar = Memory {
+            id: "dissimilar".to_string(),
+            content: "beta".to_string(),
+            embedding: Some(vec![0.0, 1.0, 0.0]),
+            timestamp: chrono::Utc::now().timestamp(),
+            importance: 0.5,
+            decay_rate: 0.1,
+            tags: vec![],
+            source: "test".to_string(),
+            session_id: None,
+            metadata: serde_json::json!({}),
+        };
+
+        store.store(&similar).await.unwrap();
+        store.store(&dissimilar).await.unwrap();
+
+        let results = store.search_semantic(&[1.0, 0.0, 0.0], 10).await.unwrap();
+        assert_eq!(results[0].id, "similar");
+    }
+
+    #[test]
+    fn embedding_round_trips_through_bytes() {
+        let original = vec![0.5_f32, -1.25, 3.0];
+        let bytes = serialize_embedding(&original);
+        assert_eq!(deserialize_embedding(&bytes), original);
+    }
+
+    #[tokio::test]
+    async fn link_artifact_is_idempotent() {
+        let store = MemoryStore::new(":memory:").await.unwrap();
+        store.link_artifact("memory-1", "digest-1").await.unwrap();
+        store.link_artifact("memory-1", "digest-1").await.unwrap();
+    }
 }
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Histogram bucket upper bounds, in milliseconds. The final bucket is
+/// implicitly `+Inf`.
+const DURATION_BUCKETS_MS: [f64; 9] = [
+    10.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0, 10_000.0,
+];
+
+/// A fixed-bucket histogram, mirroring the Prometheus histogram model
+/// (cumulative bucket counts plus a running sum and count).
+#[derive(Debug)]
+struct Histogram {
+    bucket_bounds: &'static [f64],
+    bucket_counts: Vec<AtomicU64>,
+    sum_ms: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bucket_bounds: &'static [f64]) -> Self {
+        Self {
+            bucket_bounds,
+            bucket_counts: (0..=bucket_bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_ms: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, value_ms: u64) {
+        for (i, bound) in self.bucket_bounds.iter().enumerate() {
+            if (value_ms as f64) <= *bound {
+                self.bucket_counts[i].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        // The implicit `+Inf` bucket always counts every observation.
+        self.bucket_counts[self.bucket_bounds.len()].fetch_add(1, Ordering::Relaxed);
+
+        self.sum_ms.fetch_add(value_ms, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        let mut buckets: Vec<(f64, u64)> = self
+            .bucket_bounds
+            .iter()
+            .copied()
+            .zip(self.bucket_counts.iter())
+            .map(|(bound, count)| (bound, count.load(Ordering::Relaxed)))
+            .collect();
+        buckets.push((f64::INFINITY, self.bucket_counts[self.bucket_bounds.len()].load(Ordering::Relaxed)));
+
+        HistogramSnapshot {
+            buckets,
+            sum_ms: self.sum_ms.load(Ordering::Relaxed),
+            count: self.count.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Point-in-time view of a [`Histogram`], suitable for serialization or
+/// Prometheus text-format rendering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistogramSnapshot {
+    /// `(upper_bound, cumulative_count)` pairs, ending with `(+Inf, total)`.
+    pub buckets: Vec<(f64, u64)>,
+    pub sum_ms: u64,
+    pub count: u64,
+}
+
+/// Execution metrics shared between [`crate::Runtime`] and
+/// [`crate::executor::Executor`].
+#[derive(Debug)]
+pub struct Metrics {
+    tasks_total: AtomicU64,
+    tasks_succeeded: AtomicU64,
+    tasks_failed: AtomicU64,
+    active_tasks: AtomicI64,
+    tool_invocations: RwLock<HashMap<String, u64>>,
+    task_duration: Histogram,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            tasks_total: AtomicU64::new(0),
+            tasks_succeeded: AtomicU64::new(0),
+            tasks_failed: AtomicU64::new(0),
+            active_tasks: AtomicI64::new(0),
+            tool_invocations: RwLock::new(HashMap::new()),
+            task_duration: Histogram::new(&DURATION_BUCKETS_MS),
+        }
+    }
+
+    /// Record that a task has started executing, incrementing the active
+    /// task gauge.
+    pub fn task_started(&self) {
+        self.active_tasks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record that a task has finished, decrementing the active task gauge
+    /// and recording its outcome and duration.
+    pub fn task_finished(&self, success: bool, duration_ms: u64) {
+        self.active_tasks.fetch_sub(1, Ordering::Relaxed);
+        self.tasks_total.fetch_add(1, Ordering::Relaxed);
+        if success {
+            self.tasks_succeeded.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.tasks_failed.fetch_add(1, Ordering::Relaxed);
+        }
+        self.task_duration.observe(duration_ms);
+    }
+
+    /// Record a single invocation of the named tool.
+    pub fn record_tool_invocation(&self, tool_name: &str) {
+        let mut invocations = self.tool_invocations.write().unwrap();
+        *invocations.entry(tool_name.to_string()).or_insert(0) += 1;
+    }
+
+    /// Take a serializable point-in-time snapshot of all metrics.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            tasks_total: self.tasks_total.load(Ordering::Relaxed),
+            tasks_succeeded: self.tasks_succeeded.load(Ordering::Relaxed),
+            tasks_failed: self.tasks_failed.load(Ordering::Relaxed),
+            active_tasks: self.active_tasks.load(Ordering::Relaxed),
+            tool_invocations: self.tool_invocations.read().unwrap().clone(),
+            task_duration_ms: self.task_duration.snapshot(),
+        }
+    }
+
+    /// Render all metrics in Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        self.snapshot().render_prometheus()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serializable snapshot returned by [`crate::Runtime::metrics_snapshot`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub tasks_total: u64,
+    pub tasks_succeeded: u64,
+    pub tasks_failed: u64,
+    pub active_tasks: i64,
+    pub tool_invocations: HashMap<String, u64>,
+    pub task_duration_ms: HistogramSnapshot,
+}
+
+impl MetricsSnapshot {
+    /// Render this snapshot in Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP nova_tasks_total Total tasks executed.\n");
+        out.push_str("# TYPE nova_tasks_total counter\n");
+        out.push_str(&format!("nova_tasks_total {}\n", self.tasks_total));
+
+        out.push_str("# HELP nova_tasks_succeeded_total Tasks that completed successfully.\n");
+        out.push_str("# TYPE nova_tasks_succeeded_total counter\n");
+        out.push_str(&format!("nova_tasks_succeeded_total {}\n", self.tasks_succeeded));
+
+        out.push_str("# HELP nova_tasks_failed_total Tasks that failed.\n");
+        out.push_str("# TYPE nova_tasks_failed_total counter\n");
+        out.push_str(&format!("nova_tasks_failed_total {}\n", self.tasks_failed));
+
+        out.push_str("# HELP nova_active_tasks Tasks currently executing.\n");
+        out.push_str("# TYPE nova_active_tasks gauge\n");
+        out.push_str(&format!("nova_active_tasks {}\n", self.active_tasks));
+
+        out.push_str("# HELP nova_tool_invocations_total Tool invocations, by tool name.\n");
+        out.push_str("# TYPE nova_tool_invocations_total counter\n");
+        let mut tool_names: Vec<&String> = self.tool_invocations.keys().collect();
+        tool_names.sort();
+        for name in tool_names {
+            out.push_str(&format!(
+                "nova_tool_invocations_total{{tool=\"{}\"}} {}\n",
+                name, self.tool_invocations[name]
+            ));
+        }
+
+        out.push_str("# HELP nova_task_duration_ms Task execution duration in milliseconds.\n");
+        out.push_str("# TYPE nova_task_duration_ms histogram\n");
+        for (bound, count) in &self.task_duration_ms.buckets {
+            let bound_str = if bound.is_infinite() {
+                "+Inf".to_string()
+            } else {
+                bound.to_string()
+            };
+            out.push_str(&format!(
+                "nova_task_duration_ms_bucket{{le=\"{}\"}} {}\n",
+                bound_str, count
+            ));
+        }
+        out.push_str(&format!(
+            "nova_task_duration_ms_sum {}\n",
+            self.task_duration_ms.sum_ms
+        ));
+        out.push_str(&format!(
+            "nova_task_duration_ms_count {}\n",
+            self.task_duration_ms.count
+        ));
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_task_counts_and_active_gauge() {
+        let metrics = Metrics::new();
+
+        metrics.task_started();
+        assert_eq!(metrics.snapshot().active_tasks, 1);
+
+        metrics.task_finished(true, 42);
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.active_tasks, 0);
+        assert_eq!(snapshot.tasks_total, 1);
+        assert_eq!(snapshot.tasks_succeeded, 1);
+        assert_eq!(snapshot.tasks_failed, 0);
+        assert_eq!(snapshot.task_duration_ms.count, 1);
+    }
+
+    #[test]
+    fn counts_tool_invocations_by_name() {
+        let metrics = Metrics::new();
+        metrics.record_tool_invocation("bash");
+        metrics.record_tool_invocation("bash");
+        metrics.record_tool_invocation("read");
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.tool_invocations["bash"], 2);
+        assert_eq!(snapshot.tool_invocations["read"], 1);
+    }
+
+    #[test]
+    fn prometheus_output_includes_help_and_type_lines() {
+        let metrics = Metrics::new();
+        metrics.task_finished(true, 5);
+        let text = metrics.render_prometheus();
+
+        assert!(text.contains("# HELP nova_tasks_total"));
+        assert!(text.contains("# TYPE nova_tasks_total counter"));
+        assert!(text.contains("nova_task_duration_ms_bucket{le=\"+Inf\"}"));
+    }
+}
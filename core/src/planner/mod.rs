@@ -24,7 +24,14 @@ impl Planner {
         Self {}
     }
 
-    /// Plan the execution of a task
+    /// Plan the execution of a task.
+    ///
+    /// `Task::tool_calls` carries no explicit dependency information, so
+    /// each step is chained onto the one before it by default -- the
+    /// executor runs steps with unmet dependencies strictly after the ones
+    /// they depend on, which preserves `tool_calls`'s declared order (e.g. a
+    /// `write` followed by a `read` of the same file) now that independent
+    /// steps are batched and run concurrently.
     pub async fn plan(&self, task: &crate::Task) -> Result<ExecutionPlan> {
         // TODO: Implement intelligent planning with LLM
         let steps = task
@@ -35,7 +42,11 @@ impl Planner {
                 id: format!("step-{}", i),
                 tool_name: call.tool_name.clone(),
                 parameters: call.parameters.clone(),
-                dependencies: vec![],
+                dependencies: if i == 0 {
+                    vec![]
+                } else {
+                    vec![format!("step-{}", i - 1)]
+                },
             })
             .collect();
 
@@ -51,3 +62,30 @@ impl Default for Planner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(tool_name: &str) -> crate::ToolCall {
+        crate::ToolCall {
+            tool_name: tool_name.to_string(),
+            parameters: serde_json::json!({}),
+        }
+    }
+
+    #[tokio::test]
+    async fn chains_steps_in_declared_order_by_default() {
+        let task = crate::Task {
+            id: "t1".to_string(),
+            description: "write then read".to_string(),
+            tool_calls: vec![call("write"), call("read"), call("bash")],
+        };
+
+        let plan = Planner::new().plan(&task).await.unwrap();
+
+        assert_eq!(plan.steps[0].dependencies, Vec::<String>::new());
+        assert_eq!(plan.steps[1].dependencies, vec!["step-0".to_string()]);
+        assert_eq!(plan.steps[2].dependencies, vec!["step-1".to_string()]);
+    }
+}
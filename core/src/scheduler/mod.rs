@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+
+/// When a scheduled task should fire.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Trigger {
+    /// Fire repeatedly every `Duration`.
+    Interval(Duration),
+    /// Fire once at a specific unix timestamp (seconds).
+    At(i64),
+    /// Fire according to a cron-like spec (`min hour day month weekday`).
+    Cron(String),
+}
+
+/// Whether a schedule entry runs once or repeats after each firing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Recurrence {
+    OneShot,
+    Repeating,
+}
+
+/// A task paired with its trigger and run bookkeeping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub id: String,
+    pub task: crate::Task,
+    pub trigger: Trigger,
+    pub recurrence: Recurrence,
+    pub last_run: Option<i64>,
+    pub run_count: u64,
+    pub cancelled: bool,
+}
+
+impl ScheduleEntry {
+    fn next_run_after(&self, now: i64) -> Option<i64> {
+        if self.cancelled {
+            return None;
+        }
+
+        match &self.trigger {
+            Trigger::Interval(period) => {
+                let period_secs = period.as_secs().max(1) as i64;
+                match self.last_run {
+                    Some(last) => Some(last + period_secs),
+                    None => Some(now),
+                }
+            }
+            Trigger::At(timestamp) => {
+                if self.run_count == 0 {
+                    Some(*timestamp)
+                } else {
+                    None
+                }
+            }
+            Trigger::Cron(spec) => match self.last_run {
+                Some(last) => next_cron_occurrence(spec, last),
+                // Before the first run, the current minute counts if it matches.
+                None => next_cron_occurrence(spec, now - 60),
+            },
+        }
+    }
+
+    fn is_due(&self, now: i64) -> bool {
+        matches!(self.next_run_after(now), Some(due) if due <= now)
+    }
+}
+
+/// Handle returned by [`Scheduler::add`], used to cancel a schedule entry.
+#[derive(Debug, Clone)]
+pub struct ScheduleHandle {
+    id: String,
+    entries: Arc<RwLock<HashMap<String, ScheduleEntry>>>,
+}
+
+impl ScheduleHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Cancel this entry so it is no longer considered for future runs.
+    pub async fn cancel(&self) {
+        if let Some(entry) = self.entries.write().await.get_mut(&self.id) {
+            entry.cancelled = true;
+        }
+    }
+}
+
+/// Holds the set of recurring/one-shot tasks known to a [`crate::Runtime`]
+/// and drives them through the plan -> authorize -> execute -> store pipeline
+/// as they come due.
+#[derive(Debug)]
+pub struct Scheduler {
+    entries: Arc<RwLock<HashMap<String, ScheduleEntry>>>,
+    tick: Duration,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            tick: Duration::from_secs(1),
+        }
+    }
+
+    /// Register a new schedule entry and return a handle that can cancel it.
+    pub async fn add(
+        &self,
+        task: crate::Task,
+        trigger: Trigger,
+        recurrence: Recurrence,
+    ) -> ScheduleHandle {
+        let id = uuid::Uuid::new_v4().to_string();
+        let entry = ScheduleEntry {
+            id: id.clone(),
+            task,
+            trigger,
+            recurrence,
+            last_run: None,
+            run_count: 0,
+            cancelled: false,
+        };
+
+        self.entries.write().await.insert(id.clone(), entry);
+
+        ScheduleHandle {
+            id,
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// Spawn the background loop that wakes every `tick` (one second), runs
+    /// each entry that is due through `runtime`'s pipeline, and reschedules
+    /// or retires it based on its trigger and recurrence.
+    pub fn spawn(
+        self: Arc<Self>,
+        runtime: Arc<crate::Runtime>,
+    ) -> (tokio::task::JoinHandle<()>, mpsc::Sender<()>) {
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        let tick = self.tick;
+
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(tick) => {}
+                    _ = shutdown_rx.recv() => break,
+                }
+
+                let now = runtime.now_unix();
+                let due_ids: Vec<String> = {
+                    let entries = self.entries.read().await;
+                    entries
+                        .values()
+                        .filter(|e| !e.cancelled && e.is_due(now))
+                        .map(|e| e.id.clone())
+                        .collect()
+                };
+
+                for id in due_ids {
+                    self.clone().run_entry(&id, runtime.clone(), now).await;
+                }
+            }
+        });
+
+        (handle, shutdown_tx)
+    }
+
+    async fn run_entry(self: Arc<Self>, id: &str, runtime: Arc<crate::Runtime>, now: i64) {
+        let task = {
+            let entries = self.entries.read().await;
+            match entries.get(id) {
+                Some(entry) if !entry.cancelled => entry.task.clone(),
+                _ => return,
+            }
+        };
+
+        let result = runtime.execute(task).await;
+
+        if let Ok(result) = &result {
+            let _ = runtime.store_schedule_run(id, result).await;
+        } else if let Err(err) = &result {
+            tracing::warn!(schedule_id = id, error = %err, "scheduled run failed");
+        }
+
+        let mut entries = self.entries.write().await;
+        if let Some(entry) = entries.get_mut(id) {
+            entry.last_run = Some(now);
+            entry.run_count += 1;
+            if entry.recurrence == Recurrence::OneShot {
+                entry.cancelled = true;
+            }
+        }
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Minimal cron evaluator: supports `*` and exact integer fields for
+/// `minute hour day-of-month month day-of-week`. Returns the first matching
+/// minute boundary strictly after `after`, scanning minute-by-minute up to
+/// one year out.
+fn next_cron_occurrence(spec: &str, after: i64) -> Option<i64> {
+    let fields: Vec<&str> = spec.split_whitespace().collect();
+    if fields.len() != 5 {
+        return None;
+    }
+
+    let matches = |field: &str, value: u32| field == "*" || field.parse::<u32>().ok() == Some(value);
+
+    let minute_step = 60;
+    let max_minutes_to_scan = 60 * 24 * 366;
+    let mut candidate = after - (after % minute_step) + minute_step;
+
+    for _ in 0..max_minutes_to_scan {
+        let dt = chrono::DateTime::<chrono::Utc>::from_timestamp(candidate, 0)?;
+        use chrono::{Datelike, Timelike};
+
+        let ok = matches(fields[0], dt.minute())
+            && matches(fields[1], dt.hour())
+            && matches(fields[2], dt.day())
+            && matches(fields[3], dt.month())
+            && matches(fields[4], dt.weekday().num_days_from_sunday());
+
+        if ok {
+            return Some(candidate);
+        }
+
+        candidate += minute_step;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_task() -> crate::Task {
+        crate::Task {
+            id: "scheduled-task".to_string(),
+            description: "noop".to_string(),
+            tool_calls: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn add_returns_working_handle() {
+        let scheduler = Scheduler::new();
+        let handle = scheduler
+            .add(sample_task(), Trigger::Interval(Duration::from_secs(60)), Recurrence::Repeating)
+            .await;
+
+        assert!(scheduler.entries.read().await.contains_key(handle.id()));
+
+        handle.cancel().await;
+        assert!(scheduler.entries.read().await[handle.id()].cancelled);
+    }
+
+    #[test]
+    fn interval_entry_is_due_immediately_on_first_run() {
+        let entry = ScheduleEntry {
+            id: "e1".to_string(),
+            task: sample_task(),
+            trigger: Trigger::Interval(Duration::from_secs(60)),
+            recurrence: Recurrence::Repeating,
+            last_run: None,
+            run_count: 0,
+            cancelled: false,
+        };
+
+        assert!(entry.is_due(1_000));
+    }
+
+    #[test]
+    fn one_shot_at_entry_fires_once() {
+        let mut entry = ScheduleEntry {
+            id: "e2".to_string(),
+            task: sample_task(),
+            trigger: Trigger::At(1_000),
+            recurrence: Recurrence::OneShot,
+            last_run: None,
+            run_count: 0,
+            cancelled: false,
+        };
+
+        assert!(entry.is_due(1_000));
+        entry.run_count = 1;
+        assert!(!entry.is_due(2_000));
+    }
+}
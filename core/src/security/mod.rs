@@ -1,5 +1,7 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Security manager with capability-based permissions
 #[derive(Debug)]
@@ -12,6 +14,19 @@ pub struct SecurityConfig {
     pub sandbox_mode: SandboxMode,
     pub allowed_tools: Vec<String>,
     pub denied_tools: Vec<String>,
+    /// Permissions (e.g. `"process"`, `"filesystem:read"`) that tools are
+    /// allowed to declare. A tool whose `ToolDefinition::permissions`
+    /// contains anything outside this set is denied authorization.
+    pub granted_permissions: Vec<String>,
+    /// Credentials available to tool executors, keyed by an
+    /// executor-defined name (e.g. an API key's service name).
+    #[serde(default)]
+    pub secrets: HashMap<String, String>,
+    /// Path to a JSON file of additional secrets, merged into `secrets` on
+    /// load. Lets operators keep credentials out of the serialized
+    /// `RuntimeConfig` and mount them as files in containerized deployments.
+    #[serde(default)]
+    pub secret_file: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,17 +47,82 @@ impl Default for SecurityConfig {
                 "write".to_string(),
             ],
             denied_tools: vec![],
+            granted_permissions: vec![
+                "process".to_string(),
+                "filesystem:read".to_string(),
+                "filesystem:write".to_string(),
+            ],
+            secrets: HashMap::new(),
+            secret_file: None,
         }
     }
 }
 
+impl SecurityConfig {
+    /// Load a `SecurityConfig` from a JSON file, resolving its `secret_file`
+    /// (if any) immediately so the returned config's `secrets` is complete.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("reading security config file '{}'", path.display()))?;
+        let mut config: SecurityConfig = serde_json::from_str(&contents)
+            .with_context(|| format!("parsing security config file '{}'", path.display()))?;
+
+        config.resolve_secrets()?;
+        Ok(config)
+    }
+
+    /// Merge `secret_file`'s contents (a flat JSON object of string values)
+    /// into `secrets`, erroring if a key is defined both inline and in the
+    /// file so precedence is never ambiguous. Idempotent: once a file has
+    /// been merged, `secret_file` is cleared so a second call (e.g. from
+    /// [`SecurityManager::new`] after [`SecurityConfig::from_file`] already
+    /// resolved it) is a no-op rather than re-merging and tripping the
+    /// duplicate-key check against its own previous merge.
+    pub fn resolve_secrets(&mut self) -> Result<()> {
+        let Some(path) = self.secret_file.take() else {
+            return Ok(());
+        };
+
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading secret file '{}'", path.display()))?;
+        let file_secrets: HashMap<String, String> = serde_json::from_str(&contents)
+            .with_context(|| format!("parsing secret file '{}' as a JSON object", path.display()))?;
+
+        for (key, value) in file_secrets {
+            if self.secrets.contains_key(&key) {
+                anyhow::bail!(
+                    "secret '{}' is defined both inline and in secret_file '{}'",
+                    key,
+                    path.display()
+                );
+            }
+            self.secrets.insert(key, value);
+        }
+
+        Ok(())
+    }
+}
+
 impl SecurityManager {
-    pub fn new(config: SecurityConfig) -> Self {
-        Self { config }
+    pub fn new(mut config: SecurityConfig) -> Result<Self> {
+        config.resolve_secrets()?;
+        Ok(Self { config })
+    }
+
+    /// Look up a credential made available via `SecurityConfig::secrets` or
+    /// `secret_file`.
+    pub fn credential(&self, key: &str) -> Option<&str> {
+        self.config.secrets.get(key).map(String::as_str)
     }
 
-    /// Authorize an execution plan
-    pub fn authorize(&self, plan: &crate::planner::ExecutionPlan) -> Result<()> {
+    /// Authorize an execution plan against the allow/deny lists and, for
+    /// each step's tool, against the permissions it declares in `tools`.
+    pub fn authorize(
+        &self,
+        plan: &crate::planner::ExecutionPlan,
+        tools: &crate::tools::ToolRegistry,
+    ) -> Result<()> {
         for step in &plan.steps {
             // Check if tool is allowed
             if !self.config.allowed_tools.is_empty()
@@ -55,8 +135,123 @@ impl SecurityManager {
             if self.config.denied_tools.contains(&step.tool_name) {
                 anyhow::bail!("Tool '{}' is denied", step.tool_name);
             }
+
+            let definition = tools
+                .get(&step.tool_name)
+                .ok_or_else(|| anyhow::anyhow!("Tool '{}' is not registered", step.tool_name))?;
+
+            for permission in &definition.permissions {
+                if !self.config.granted_permissions.contains(permission) {
+                    anyhow::bail!(
+                        "Tool '{}' requires permission '{}' which is not granted",
+                        step.tool_name,
+                        permission
+                    );
+                }
+            }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planner::{ExecutionPlan, ExecutionStep};
+    use crate::tools::ToolRegistry;
+
+    fn plan_for(tool_name: &str) -> ExecutionPlan {
+        ExecutionPlan {
+            task_id: "t1".to_string(),
+            steps: vec![ExecutionStep {
+                id: "step-0".to_string(),
+                tool_name: tool_name.to_string(),
+                parameters: serde_json::json!({}),
+                dependencies: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn denies_tool_requiring_ungranted_permission() {
+        let config = SecurityConfig {
+            granted_permissions: vec![],
+            ..Default::default()
+        };
+        let manager = SecurityManager::new(config).unwrap();
+        let tools = ToolRegistry::new(SandboxMode::None, std::path::PathBuf::from("."));
+
+        assert!(manager.authorize(&plan_for("bash"), &tools).is_err());
+    }
+
+    #[test]
+    fn allows_tool_with_granted_permissions() {
+        let manager = SecurityManager::new(SecurityConfig::default()).unwrap();
+        let tools = ToolRegistry::new(SandboxMode::None, std::path::PathBuf::from("."));
+
+        assert!(manager.authorize(&plan_for("bash"), &tools).is_ok());
+    }
+
+    #[test]
+    fn resolve_secrets_merges_file_contents() {
+        let dir = std::env::temp_dir().join(format!("nova-secrets-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let secret_path = dir.join("secrets.json");
+        std::fs::write(&secret_path, r#"{"openai_api_key": "sk-from-file"}"#).unwrap();
+
+        let mut config = SecurityConfig {
+            secret_file: Some(secret_path),
+            ..Default::default()
+        };
+        config.resolve_secrets().unwrap();
+
+        assert_eq!(config.secrets.get("openai_api_key").unwrap(), "sk-from-file");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn from_file_then_security_manager_new_does_not_re_merge() {
+        let dir = std::env::temp_dir().join(format!("nova-secrets-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let secret_path = dir.join("secrets.json");
+        std::fs::write(&secret_path, r#"{"openai_api_key": "sk-from-file"}"#).unwrap();
+
+        let config_path = dir.join("config.json");
+        let config_json = serde_json::json!({
+            "sandbox_mode": "Process",
+            "allowed_tools": ["bash"],
+            "denied_tools": [],
+            "granted_permissions": ["process"],
+            "secrets": {},
+            "secret_file": secret_path,
+        });
+        std::fs::write(&config_path, serde_json::to_string(&config_json).unwrap()).unwrap();
+
+        let config = SecurityConfig::from_file(&config_path).unwrap();
+        let manager = SecurityManager::new(config).unwrap();
+
+        assert_eq!(manager.credential("openai_api_key"), Some("sk-from-file"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn resolve_secrets_errors_on_duplicate_key() {
+        let dir = std::env::temp_dir().join(format!("nova-secrets-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let secret_path = dir.join("secrets.json");
+        std::fs::write(&secret_path, r#"{"openai_api_key": "sk-from-file"}"#).unwrap();
+
+        let mut config = SecurityConfig {
+            secret_file: Some(secret_path),
+            ..Default::default()
+        };
+        config.secrets.insert("openai_api_key".to_string(), "sk-inline".to_string());
+
+        assert!(config.resolve_secrets().is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
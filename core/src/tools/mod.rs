@@ -1,11 +1,30 @@
-use anyhow::Result;
+use crate::security::SandboxMode;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+
+/// A tool backend: given a step's parameters, actually perform the work and
+/// return its JSON result. Implementors are registered alongside a
+/// [`ToolDefinition`] in the [`ToolRegistry`].
+#[async_trait]
+pub trait ToolExecutor: Send + Sync + std::fmt::Debug {
+    async fn invoke(&self, params: &serde_json::Value) -> Result<serde_json::Value>;
+}
 
 /// Tool registry for managing available tools
 #[derive(Debug, Clone)]
 pub struct ToolRegistry {
-    tools: HashMap<String, ToolDefinition>,
+    tools: HashMap<String, RegisteredTool>,
+}
+
+#[derive(Debug, Clone)]
+struct RegisteredTool {
+    definition: ToolDefinition,
+    executor: Arc<dyn ToolExecutor>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,20 +36,20 @@ pub struct ToolDefinition {
 }
 
 impl ToolRegistry {
-    pub fn new() -> Self {
+    /// Create a registry populated with the built-in `bash`, `read`, and
+    /// `write` tools. `sandbox_mode` governs how `bash` spawns its child
+    /// process; `workspace_root` confines `read`/`write` to a directory tree.
+    pub fn new(sandbox_mode: SandboxMode, workspace_root: PathBuf) -> Self {
         let mut registry = Self {
             tools: HashMap::new(),
         };
 
-        // Register built-in tools
-        registry.register_builtin_tools();
+        registry.register_builtin_tools(sandbox_mode, workspace_root);
         registry
     }
 
-    fn register_builtin_tools(&mut self) {
-        // Bash tool
-        self.tools.insert(
-            "bash".to_string(),
+    fn register_builtin_tools(&mut self, sandbox_mode: SandboxMode, workspace_root: PathBuf) {
+        self.register(
             ToolDefinition {
                 name: "bash".to_string(),
                 description: "Execute shell commands".to_string(),
@@ -46,11 +65,13 @@ impl ToolRegistry {
                 }),
                 permissions: vec!["process".to_string()],
             },
+            Arc::new(BashExecutor {
+                sandbox_mode,
+                workspace_root: workspace_root.clone(),
+            }),
         );
 
-        // Read file tool
-        self.tools.insert(
-            "read".to_string(),
+        self.register(
             ToolDefinition {
                 name: "read".to_string(),
                 description: "Read file contents".to_string(),
@@ -59,27 +80,244 @@ impl ToolRegistry {
                     "properties": {
                         "path": {
                             "type": "string",
-                            "description": "File path to read"
+                            "description": "File path to read, relative to the sandboxed root"
                         }
                     },
                     "required": ["path"]
                 }),
                 permissions: vec!["filesystem:read".to_string()],
             },
+            Arc::new(FsReadExecutor {
+                root: workspace_root.clone(),
+            }),
+        );
+
+        self.register(
+            ToolDefinition {
+                name: "write".to_string(),
+                description: "Write file contents".to_string(),
+                parameters_schema: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "File path to write, relative to the sandboxed root"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "Content to write to the file"
+                        }
+                    },
+                    "required": ["path", "content"]
+                }),
+                permissions: vec!["filesystem:write".to_string()],
+            },
+            Arc::new(FsWriteExecutor {
+                root: workspace_root,
+            }),
         );
     }
 
     pub fn get(&self, name: &str) -> Option<&ToolDefinition> {
-        self.tools.get(name)
+        self.tools.get(name).map(|t| &t.definition)
+    }
+
+    pub fn get_executor(&self, name: &str) -> Option<Arc<dyn ToolExecutor>> {
+        self.tools.get(name).map(|t| t.executor.clone())
+    }
+
+    pub fn register(&mut self, tool: ToolDefinition, executor: Arc<dyn ToolExecutor>) {
+        self.tools.insert(
+            tool.name.clone(),
+            RegisteredTool {
+                definition: tool,
+                executor,
+            },
+        );
+    }
+}
+
+/// Resolve a user-supplied relative path against a sandboxed root, rejecting
+/// absolute paths and any `..` component that could escape it.
+fn resolve_sandboxed_path(root: &Path, requested: &str) -> Result<PathBuf> {
+    let requested_path = Path::new(requested);
+
+    if requested_path.is_absolute() {
+        anyhow::bail!("path '{}' must be relative to the sandboxed root", requested);
+    }
+
+    for component in requested_path.components() {
+        if matches!(component, std::path::Component::ParentDir) {
+            anyhow::bail!("path '{}' must not contain '..' components", requested);
+        }
+    }
+
+    Ok(root.join(requested_path))
+}
+
+/// Runs shell commands. In `SandboxMode::Process`, the child is spawned with
+/// a cleared, minimal environment (`PATH` only). In every mode stdin is not
+/// inherited and `workspace_root` is the working directory. The command
+/// itself is not confined: absolute paths and `..` still reach files outside
+/// `workspace_root`.
+#[derive(Debug)]
+struct BashExecutor {
+    sandbox_mode: SandboxMode,
+    workspace_root: PathBuf,
+}
+
+#[async_trait]
+impl ToolExecutor for BashExecutor {
+    async fn invoke(&self, params: &serde_json::Value) -> Result<serde_json::Value> {
+        let command = params
+            .get("command")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("bash tool requires a 'command' string parameter"))?;
+
+        let mut cmd = tokio::process::Command::new("/bin/sh");
+        cmd.arg("-c").arg(command);
+        cmd.current_dir(&self.workspace_root);
+        cmd.stdin(Stdio::null());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+
+        match self.sandbox_mode {
+            SandboxMode::Process => {
+                cmd.env_clear();
+                cmd.env("PATH", "/usr/bin:/bin");
+            }
+            SandboxMode::None => {}
+            SandboxMode::Container | SandboxMode::VM => {
+                anyhow::bail!(
+                    "sandbox mode {:?} is not yet implemented by the built-in bash executor",
+                    self.sandbox_mode
+                );
+            }
+        }
+
+        let output = cmd.output().await?;
+
+        Ok(serde_json::json!({
+            "stdout": String::from_utf8_lossy(&output.stdout),
+            "stderr": String::from_utf8_lossy(&output.stderr),
+            "exit_code": output.status.code(),
+        }))
+    }
+}
+
+/// Reads files confined to `root`.
+#[derive(Debug)]
+struct FsReadExecutor {
+    root: PathBuf,
+}
+
+#[async_trait]
+impl ToolExecutor for FsReadExecutor {
+    async fn invoke(&self, params: &serde_json::Value) -> Result<serde_json::Value> {
+        let path = params
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("read tool requires a 'path' string parameter"))?;
+
+        let resolved = resolve_sandboxed_path(&self.root, path)?;
+        let content = tokio::fs::read_to_string(&resolved).await?;
+
+        Ok(serde_json::json!({ "content": content }))
     }
+}
+
+/// Writes files confined to `root`, creating parent directories as needed.
+#[derive(Debug)]
+struct FsWriteExecutor {
+    root: PathBuf,
+}
+
+#[async_trait]
+impl ToolExecutor for FsWriteExecutor {
+    async fn invoke(&self, params: &serde_json::Value) -> Result<serde_json::Value> {
+        let path = params
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("write tool requires a 'path' string parameter"))?;
+        let content = params
+            .get("content")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("write tool requires a 'content' string parameter"))?;
 
-    pub fn register(&mut self, tool: ToolDefinition) {
-        self.tools.insert(tool.name.clone(), tool);
+        let resolved = resolve_sandboxed_path(&self.root, path)?;
+        if let Some(parent) = resolved.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&resolved, content).await?;
+
+        Ok(serde_json::json!({ "bytes_written": content.len() }))
     }
 }
 
-impl Default for ToolRegistry {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_absolute_paths() {
+        let root = PathBuf::from("/sandbox");
+        assert!(resolve_sandboxed_path(&root, "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn rejects_parent_dir_escape() {
+        let root = PathBuf::from("/sandbox");
+        assert!(resolve_sandboxed_path(&root, "../secrets.txt").is_err());
+    }
+
+    #[test]
+    fn resolves_relative_paths_under_root() {
+        let root = PathBuf::from("/sandbox");
+        let resolved = resolve_sandboxed_path(&root, "notes/todo.txt").unwrap();
+        assert_eq!(resolved, PathBuf::from("/sandbox/notes/todo.txt"));
+    }
+
+    #[tokio::test]
+    async fn bash_runs_with_workspace_root_as_cwd() {
+        let root = std::env::temp_dir().join(format!("nova-tools-test-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&root).await.unwrap();
+
+        let bash = BashExecutor {
+            sandbox_mode: SandboxMode::Process,
+            workspace_root: root.clone(),
+        };
+        let result = bash
+            .invoke(&serde_json::json!({"command": "pwd"}))
+            .await
+            .unwrap();
+
+        let pwd = result["stdout"].as_str().unwrap().trim();
+        assert_eq!(
+            std::fs::canonicalize(pwd).unwrap(),
+            std::fs::canonicalize(&root).unwrap()
+        );
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn write_then_read_round_trips() {
+        let root = std::env::temp_dir().join(format!("nova-tools-test-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&root).await.unwrap();
+
+        let writer = FsWriteExecutor { root: root.clone() };
+        writer
+            .invoke(&serde_json::json!({"path": "out.txt", "content": "hello"}))
+            .await
+            .unwrap();
+
+        let reader = FsReadExecutor { root: root.clone() };
+        let result = reader
+            .invoke(&serde_json::json!({"path": "out.txt"}))
+            .await
+            .unwrap();
+        assert_eq!(result["content"], "hello");
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
     }
 }